@@ -8,27 +8,57 @@ use fastcrypto::encoding::{Base58, Encoding};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Bytes};
-
-/// A representation of a 32 byte digest
+// `subtle` (constant-time comparisons), `hex` (hex parsing), and `thiserror` (structured
+// parse errors) are new to this module; `bcs` is used only by the test module below, as
+// a dev-dependency. Make sure all four are present in this crate's Cargo.toml.
+use subtle::{Choice, ConstantTimeEq};
+
+/// A representation of an `N` byte digest.
+///
+/// Most digests in this crate are 32 bytes (see the [`Digest32`] alias, used by
+/// [`TransactionDigest`], [`ObjectDigest`], and friends below), but the length is a
+/// const parameter so that larger hash outputs (e.g. a future 64 byte algorithm) can
+/// reuse the same type instead of duplicating it.
 #[serde_as]
-#[derive(
-    Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
-)]
-pub struct Digest(
+#[derive(Clone, Copy, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+pub struct Digest<const N: usize>(
     #[schemars(with = "Base58")]
     #[serde_as(as = "Readable<Base58, Bytes>")]
-    [u8; 32],
+    [u8; N],
 );
 
-impl Digest {
-    pub const ZERO: Self = Digest([0; 32]);
+impl<const N: usize> Default for Digest<N> {
+    // `[u8; N]` only implements `Default` for `N <= 32`, so a derived `Default` fails to
+    // compile for this generic type; implement it directly instead.
+    fn default() -> Self {
+        Self([0u8; N])
+    }
+}
+
+/// The digest length used throughout this crate today.
+pub type Digest32 = Digest<32>;
 
-    pub const fn new(digest: [u8; 32]) -> Self {
+/// Identifies the hash function that produced a digest.
+///
+/// `Digest<N>` itself stays bare bytes on purpose -- it's `Copy`, has a fixed in-memory
+/// layout, and is what BCS serializes on the wire today, none of which we want to
+/// disturb by smuggling an algorithm tag into the struct. Self-description instead
+/// lives at the boundary where it's actually needed: [`Digest::to_multihash`] pairs a
+/// digest with a [`DigestAlgorithm`] (as a multicodec-coded prefix) when exporting to
+/// other systems, and [`Digest::from_multihash`] recovers both from that encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DigestAlgorithm {
+    Blake2b256,
+    Sha3_256,
+}
+
+impl<const N: usize> Digest<N> {
+    pub const fn new(digest: [u8; N]) -> Self {
         Self(digest)
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(mut rng: R) -> Self {
-        let mut bytes = [0; 32];
+        let mut bytes = [0; N];
         rng.fill_bytes(&mut bytes);
         Self(bytes)
     }
@@ -37,59 +67,143 @@ impl Digest {
         Self::generate(rand::thread_rng())
     }
 
-    pub const fn inner(&self) -> &[u8; 32] {
+    pub const fn inner(&self) -> &[u8; N] {
         &self.0
     }
 
-    pub const fn into_inner(self) -> [u8; 32] {
+    pub const fn into_inner(self) -> [u8; N] {
         self.0
     }
+
+    /// Compare two digests in constant time.
+    ///
+    /// Unlike a byte-wise `==`, this never branches on the digest contents, so it
+    /// doesn't leak timing information about where two digests first differ. The
+    /// `PartialEq` impl for `Digest` (and so the derived `PartialEq` of the wrapper types
+    /// below) goes through this method, so `==` on digests is constant-time as well.
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        let mut diff = 0u8;
+        for (a, b) in self.0.iter().zip(other.0.iter()) {
+            diff |= a ^ b;
+        }
+        diff.ct_eq(&0)
+    }
+}
+
+impl<const N: usize> Digest<N> {
+    pub const ZERO: Self = Digest([0; N]);
+}
+
+impl<const N: usize> ConstantTimeEq for Digest<N> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Digest::ct_eq(self, other)
+    }
 }
 
-impl AsRef<[u8]> for Digest {
+impl<const N: usize> PartialEq for Digest<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const N: usize> Eq for Digest<N> {}
+
+// `Hash` can't be derived alongside our manual `PartialEq`/`ConstantTimeEq` (clippy
+// flags that combination as error-prone since a derived hash could disagree with a
+// hand-written equality). Here they do agree: both operate byte-for-byte over `self.0`,
+// so hashing isn't a new way to distinguish equal digests.
+impl<const N: usize> std::hash::Hash for Digest<N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Digest<N> {
     fn as_ref(&self) -> &[u8] {
         &self.0
     }
 }
 
-impl AsRef<[u8; 32]> for Digest {
-    fn as_ref(&self) -> &[u8; 32] {
+impl<const N: usize> AsRef<[u8; N]> for Digest<N> {
+    fn as_ref(&self) -> &[u8; N] {
         &self.0
     }
 }
 
-impl From<Digest> for [u8; 32] {
-    fn from(digest: Digest) -> Self {
+impl<const N: usize> From<Digest<N>> for [u8; N] {
+    fn from(digest: Digest<N>) -> Self {
         digest.into_inner()
     }
 }
 
-impl From<[u8; 32]> for Digest {
-    fn from(digest: [u8; 32]) -> Self {
+impl<const N: usize> From<[u8; N]> for Digest<N> {
+    fn from(digest: [u8; N]) -> Self {
         Self::new(digest)
     }
 }
 
-impl From<fastcrypto::hash::Digest<32>> for Digest {
-    fn from(digest: fastcrypto::hash::Digest<32>) -> Self {
+impl<const N: usize> From<fastcrypto::hash::Digest<N>> for Digest<N> {
+    fn from(digest: fastcrypto::hash::Digest<N>) -> Self {
         Self::new(digest.digest)
     }
 }
 
-impl fmt::Display for Digest {
+/// Base58 alphabet (Bitcoin's), used to render digests without an intermediate `String`.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+impl<const N: usize> fmt::Display for Digest<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // TODO avoid the allocation
-        f.write_str(&Base58::encode(self.0))
+        // Treat the digest as a big-endian integer and repeatedly divide by 58,
+        // writing directly into stack buffers so this never allocates. Base58 expands N
+        // bytes to at most ~1.37*N characters, but stable Rust's const generics only
+        // allow bare `N` (not an expression like `N * 2`) as an array length, so we get
+        // a working capacity that's a multiple of N by using `[[u8; N]; K]` and
+        // indexing into it with plain div/mod. `K = 2` for the digit buffer and `K = 3`
+        // for the final output (leading '1's plus digits) are comfortably above that
+        // 1.37*N bound for every N >= 1, so this never panics on a valid digest.
+        let mut working = self.0;
+        let leading_zeros = working.iter().take_while(|&&b| b == 0).count();
+
+        let mut digits: [[u8; N]; 2] = [[0u8; N]; 2];
+        let mut len = 0usize;
+        // Only record a digit while `working` is still nonzero, so an all-zero digest
+        // (or one whose significant part we've already fully divided out) contributes
+        // zero digits -- its Display is just the leading '1's computed below.
+        while working.iter().any(|&b| b != 0) {
+            let mut remainder = 0u32;
+            for byte in working.iter_mut() {
+                let acc = (remainder << 8) | *byte as u32;
+                *byte = (acc / 58) as u8;
+                remainder = acc % 58;
+            }
+            digits[len / N][len % N] = remainder as u8;
+            len += 1;
+        }
+
+        let mut out: [[u8; N]; 3] = [[0u8; N]; 3];
+        for i in 0..leading_zeros {
+            out[i / N][i % N] = b'1';
+        }
+        for i in 0..len {
+            let digit = digits[(len - 1 - i) / N][(len - 1 - i) % N];
+            out[(leading_zeros + i) / N][(leading_zeros + i) % N] =
+                BASE58_ALPHABET[digit as usize];
+        }
+
+        for i in 0..(leading_zeros + len) {
+            write!(f, "{}", out[i / N][i % N] as char)?;
+        }
+        Ok(())
     }
 }
 
-impl fmt::Debug for Digest {
+impl<const N: usize> fmt::Debug for Digest<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self, f)
     }
 }
 
-impl fmt::LowerHex for Digest {
+impl<const N: usize> fmt::LowerHex for Digest<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
             write!(f, "0x")?;
@@ -103,7 +217,7 @@ impl fmt::LowerHex for Digest {
     }
 }
 
-impl fmt::UpperHex for Digest {
+impl<const N: usize> fmt::UpperHex for Digest<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
             write!(f, "0x")?;
@@ -117,23 +231,246 @@ impl fmt::UpperHex for Digest {
     }
 }
 
+/// Errors returned when parsing a [`Digest`] from a string.
+#[derive(Debug, thiserror::Error)]
+pub enum DigestParseError {
+    #[error("invalid digest length: expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    #[error("invalid hex digest: {0}")]
+    InvalidHex(String),
+    #[error("invalid base58 digest: {0}")]
+    InvalidBase58(String),
+}
+
+impl<const N: usize> Digest<N> {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DigestParseError> {
+        let arr: [u8; N] = bytes
+            .try_into()
+            .map_err(|_| DigestParseError::InvalidLength {
+                expected: N,
+                actual: bytes.len(),
+            })?;
+        Ok(Self::new(arr))
+    }
+
+    /// Parse a digest from its `0x`-prefixed hex representation (matching the output of
+    /// this type's `LowerHex`/`UpperHex` impls).
+    pub fn from_hex(s: &str) -> Result<Self, DigestParseError> {
+        let s = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let bytes = hex::decode(s).map_err(|e| DigestParseError::InvalidHex(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a digest from its Base58 representation (matching the output of this type's
+    /// `Display`).
+    pub fn from_base58(s: &str) -> Result<Self, DigestParseError> {
+        let bytes =
+            Base58::decode(s).map_err(|e| DigestParseError::InvalidBase58(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl<const N: usize> std::str::FromStr for Digest<N> {
+    type Err = DigestParseError;
+
+    /// Parse a digest, auto-detecting the encoding: `0x`/`0X`-prefixed input is treated
+    /// as hex, anything else as Base58.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") || s.starts_with("0X") {
+            Self::from_hex(s)
+        } else {
+            Self::from_base58(s)
+        }
+    }
+}
+
+impl DigestAlgorithm {
+    /// The multicodec code identifying this hash function, used by [`Digest::to_multihash`]
+    /// and [`Digest::from_multihash`]. See the multicodec table at
+    /// <https://github.com/multiformats/multicodec/blob/master/table.csv>.
+    pub const fn multicodec(self) -> u64 {
+        match self {
+            DigestAlgorithm::Sha3_256 => 0x16,
+            DigestAlgorithm::Blake2b256 => 0xb220,
+        }
+    }
+
+    fn from_multicodec(code: u64) -> Option<Self> {
+        match code {
+            0x16 => Some(DigestAlgorithm::Sha3_256),
+            0xb220 => Some(DigestAlgorithm::Blake2b256),
+            _ => None,
+        }
+    }
+}
+
+/// Errors returned when decoding a multihash with [`Digest::from_multihash`].
+#[derive(Debug, thiserror::Error)]
+pub enum MultihashError {
+    #[error("truncated multihash: could not read {0}")]
+    Truncated(&'static str),
+    #[error("unknown hash algorithm multicodec: {0:#x}")]
+    UnknownAlgorithm(u64),
+    #[error("multihash length mismatch: header declared {declared} bytes, found {actual}")]
+    LengthMismatch { declared: usize, actual: usize },
+}
+
+/// Writes `value` as an unsigned LEB128 varint, per the multihash spec.
+fn write_uvarint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Reads an unsigned LEB128 varint, returning its value and the remaining bytes.
+fn read_uvarint(bytes: &[u8]) -> Result<(u64, &[u8]), MultihashError> {
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    for (i, &byte) in bytes.iter().enumerate() {
+        // A u64 holds at most 10 groups of 7 bits; bail before `<<` overflows on a
+        // malformed varint with too many continuation bytes instead of panicking.
+        if shift >= 64 {
+            return Err(MultihashError::Truncated("varint (too long)"));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+        shift += 7;
+    }
+    Err(MultihashError::Truncated("varint"))
+}
+
+impl<const N: usize> Digest<N> {
+    /// Encode this digest as a self-describing [multihash](https://multiformats.io/multihash/):
+    /// `varint(algorithm code) || varint(length) || bytes`. Unlike the bare digest bytes used
+    /// internally, this carries enough information for another system to know how to
+    /// interpret and validate it.
+    pub fn to_multihash(&self, algo: DigestAlgorithm) -> Vec<u8> {
+        let mut out = Vec::with_capacity(N + 10);
+        write_uvarint(algo.multicodec(), &mut out);
+        write_uvarint(N as u64, &mut out);
+        out.extend_from_slice(&self.0);
+        out
+    }
+
+    /// Decode a digest previously produced by [`Digest::to_multihash`], returning the
+    /// algorithm it was tagged with along with the digest itself.
+    pub fn from_multihash(bytes: &[u8]) -> Result<(DigestAlgorithm, Self), MultihashError> {
+        let (code, rest) = read_uvarint(bytes)?;
+        let algo =
+            DigestAlgorithm::from_multicodec(code).ok_or(MultihashError::UnknownAlgorithm(code))?;
+        let (len, rest) = read_uvarint(rest)?;
+        if rest.len() != len as usize {
+            return Err(MultihashError::LengthMismatch {
+                declared: len as usize,
+                actual: rest.len(),
+            });
+        }
+        let digest = Self::from_bytes(rest).map_err(|_| MultihashError::LengthMismatch {
+            declared: N,
+            actual: rest.len(),
+        })?;
+        Ok((algo, digest))
+    }
+}
+
+/// A borrowed view over a [`Digest`]'s bytes.
+///
+/// Ordinary [`Digest`] deserialization copies the bytes out of the input buffer. When
+/// parsing something like checkpoint contents, which can hold thousands of digests, that
+/// copy adds up. `DigestRef` instead borrows directly from the deserializer's input.
+///
+/// This only works with non-human-readable (binary) formats, like `bcs`, whose
+/// deserializer can hand back a slice borrowed from the original input (`bcs` does this
+/// via `visit_borrowed_bytes`). Formats that only ever hand over owned or transient byte
+/// buffers (e.g. most human-readable formats, or binary formats reading from a non-slice
+/// source) have nothing to borrow from and will fail to deserialize a `DigestRef`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DigestRef<'a, const N: usize>(&'a [u8; N]);
+
+impl<'a, const N: usize> DigestRef<'a, N> {
+    pub fn to_owned(self) -> Digest<N> {
+        Digest::new(*self.0)
+    }
+}
+
+impl<'a, const N: usize> AsRef<[u8]> for DigestRef<'a, N> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<'a, 'de: 'a, const N: usize> Deserialize<'de> for DigestRef<'a, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct BorrowedDigestVisitor<const N: usize>;
+
+        impl<'de, const N: usize> serde::de::Visitor<'de> for BorrowedDigestVisitor<N> {
+            type Value = &'de [u8; N];
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} borrowed digest bytes", N)
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                v.try_into()
+                    .map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            // Reached when the deserializer only has transient (non-`'de`) bytes to
+            // offer, e.g. because its input isn't a contiguous slice. There's nothing
+            // to borrow from in that case, so fail clearly instead of silently falling
+            // back to an owned copy, which would defeat the point of `DigestRef`.
+            fn visit_bytes<E>(self, _v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Err(E::custom(
+                    "DigestRef requires a deserializer that can hand out borrowed bytes (e.g. bcs)",
+                ))
+            }
+        }
+
+        deserializer
+            .deserialize_bytes(BorrowedDigestVisitor::<N>)
+            .map(DigestRef)
+    }
+}
+
 /// Representation of a Checkpoint's digest
 #[derive(
     Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
 )]
-pub struct CheckpointDigest(Digest);
+pub struct CheckpointDigest(Digest32);
 
 impl CheckpointDigest {
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(rng: R) -> Self {
-        Self(Digest::generate(rng))
+        Self(Digest32::generate(rng))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 
     pub const fn inner(&self) -> &[u8; 32] {
@@ -201,26 +538,24 @@ impl std::str::FromStr for CheckpointDigest {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = [0; 32];
-        result.copy_from_slice(&Base58::decode(s).map_err(|e| anyhow::anyhow!(e))?);
-        Ok(CheckpointDigest::new(result))
+        Ok(Self(s.parse::<Digest32>()?))
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
-pub struct CheckpointContentsDigest(Digest);
+pub struct CheckpointContentsDigest(Digest32);
 
 impl CheckpointContentsDigest {
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(rng: R) -> Self {
-        Self(Digest::generate(rng))
+        Self(Digest32::generate(rng))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 
     pub const fn inner(&self) -> &[u8; 32] {
@@ -288,7 +623,7 @@ impl fmt::UpperHex for CheckpointContentsDigest {
 
 /// A transaction will have a (unique) digest.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
-pub struct TransactionDigest(Digest);
+pub struct TransactionDigest(Digest32);
 
 impl Default for TransactionDigest {
     fn default() -> Self {
@@ -297,10 +632,10 @@ impl Default for TransactionDigest {
 }
 
 impl TransactionDigest {
-    pub const ZERO: Self = Self(Digest::ZERO);
+    pub const ZERO: Self = Self(Digest32::ZERO);
 
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     /// A digest we use to signify the parent transaction was the genesis,
@@ -311,11 +646,11 @@ impl TransactionDigest {
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(rng: R) -> Self {
-        Self(Digest::generate(rng))
+        Self(Digest32::generate(rng))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 
     pub fn inner(&self) -> &[u8; 32] {
@@ -394,28 +729,26 @@ impl std::str::FromStr for TransactionDigest {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = [0; 32];
-        result.copy_from_slice(&Base58::decode(s).map_err(|e| anyhow::anyhow!(e))?);
-        Ok(TransactionDigest::new(result))
+        Ok(Self(s.parse::<Digest32>()?))
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
-pub struct TransactionEffectsDigest(Digest);
+pub struct TransactionEffectsDigest(Digest32);
 
 impl TransactionEffectsDigest {
-    pub const ZERO: Self = Self(Digest::ZERO);
+    pub const ZERO: Self = Self(Digest32::ZERO);
 
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(rng: R) -> Self {
-        Self(Digest::generate(rng))
+        Self(Digest32::generate(rng))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 
     pub const fn inner(&self) -> &[u8; 32] {
@@ -483,17 +816,17 @@ impl fmt::UpperHex for TransactionEffectsDigest {
 
 #[serde_as]
 #[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Serialize, Deserialize, JsonSchema)]
-pub struct TransactionEventsDigest(Digest);
+pub struct TransactionEventsDigest(Digest32);
 
 impl TransactionEventsDigest {
-    pub const ZERO: Self = Self(Digest::ZERO);
+    pub const ZERO: Self = Self(Digest32::ZERO);
 
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 }
 
@@ -507,7 +840,7 @@ impl fmt::Debug for TransactionEventsDigest {
 
 // Each object has a unique digest
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
-pub struct ObjectDigest(Digest);
+pub struct ObjectDigest(Digest32);
 
 impl ObjectDigest {
     pub const MIN: ObjectDigest = Self::new([u8::MIN; 32]);
@@ -524,15 +857,15 @@ impl ObjectDigest {
         Self::new([Self::OBJECT_DIGEST_WRAPPED_BYTE_VAL; 32]);
 
     pub const fn new(digest: [u8; 32]) -> Self {
-        Self(Digest::new(digest))
+        Self(Digest32::new(digest))
     }
 
     pub fn generate<R: rand::RngCore + rand::CryptoRng>(rng: R) -> Self {
-        Self(Digest::generate(rng))
+        Self(Digest32::generate(rng))
     }
 
     pub fn random() -> Self {
-        Self(Digest::random())
+        Self(Digest32::random())
     }
 
     pub const fn inner(&self) -> &[u8; 32] {
@@ -615,8 +948,65 @@ impl std::str::FromStr for ObjectDigest {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = [0; 32];
-        result.copy_from_slice(&Base58::decode(s).map_err(|e| anyhow::anyhow!(e))?);
-        Ok(ObjectDigest::new(result))
+        Ok(Self(s.parse::<Digest32>()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base58_display_round_trips() {
+        let digest = Digest32::new([7; 32]);
+        let encoded = digest.to_string();
+        assert_eq!(encoded.parse::<Digest32>().unwrap(), digest);
+    }
+
+    #[test]
+    fn base58_display_of_zero_digest_has_no_extra_leading_one() {
+        // Regression test: the digit-generation loop used to push a digit before
+        // checking whether the working value was zero, so `Digest::ZERO` displayed as
+        // 33 '1's instead of 32 and failed to round-trip through `FromStr`.
+        let zero = Digest32::ZERO;
+        assert_eq!(zero.to_string(), "1".repeat(32));
+        assert_eq!(zero.to_string().parse::<Digest32>().unwrap(), zero);
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let digest = Digest32::new([9; 32]);
+        let hex = format!("{:#x}", digest);
+        assert_eq!(Digest32::from_hex(&hex).unwrap(), digest);
+        assert_eq!(hex.parse::<Digest32>().unwrap(), digest);
+    }
+
+    #[test]
+    fn from_str_rejects_wrong_length_instead_of_panicking() {
+        let err = "1111".parse::<Digest32>().unwrap_err();
+        assert!(matches!(err, DigestParseError::InvalidLength { .. }));
+    }
+
+    #[test]
+    fn multihash_round_trips() {
+        let digest = Digest32::random();
+        let encoded = digest.to_multihash(DigestAlgorithm::Blake2b256);
+        let (algo, decoded) = Digest32::from_multihash(&encoded).unwrap();
+        assert_eq!(algo, DigestAlgorithm::Blake2b256);
+        assert_eq!(decoded, digest);
+    }
+
+    #[test]
+    fn multihash_rejects_overlong_varint_instead_of_panicking() {
+        let malformed = [0x80u8; 11];
+        assert!(Digest32::from_multihash(&malformed).is_err());
+    }
+
+    #[test]
+    fn digest_ref_borrows_from_bcs_bytes() {
+        let digest = Digest32::random();
+        let bytes = bcs::to_bytes(&digest).unwrap();
+        let digest_ref: DigestRef<'_, 32> = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(digest_ref.to_owned(), digest);
     }
 }